@@ -0,0 +1,351 @@
+//! A small input-abstraction layer sitting between raw ggez events and
+//! `Scene`s.
+//!
+//! Instead of every `Scene` matching on `Keycode`/`MouseButton` values
+//! directly, an `InputBinding` maps those physical inputs to named
+//! logical actions (`ActionId`s), and an `InputArbiter` turns the raw
+//! ggez callbacks into per-frame `InputState` that scenes can poll with
+//! `get_button`/`get_axis`.  This is what makes controls remappable and
+//! keeps a scene's logic decoupled from exactly which key or button
+//! triggers it.
+
+use ggez::event::{Keycode, MouseButton};
+
+use std::collections::BTreeMap;
+use std::collections::HashMap;
+use std::collections::HashSet;
+
+/// The name of a logical action, e.g. `"jump"` or `"move_x"`.
+pub type ActionId = String;
+
+/// A raw physical input an `InputBinding` can be registered against.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+enum RawInput {
+    Key(Keycode),
+    MouseButton(MouseButton),
+    MouseAxis { horizontal: bool },
+}
+
+/// What a `RawInput` feeds into once it fires.
+#[derive(Clone, Debug)]
+enum InputEffect {
+    /// A digital on/off action.
+    Button(ActionId),
+    /// Contributes to a continuous axis.  `scale` is how much a single
+    /// press (or a mouse-motion delta) adds to the axis value -- bind
+    /// opposing keys with opposite signs to get a -1..1 axis out of two
+    /// buttons.  `deadzone` is the minimum `|value|` before it counts as
+    /// non-zero.
+    Axis {
+        id: ActionId,
+        scale: f32,
+        deadzone: f32,
+    },
+}
+
+/// The registry of `RawInput -> InputEffect` mappings.  Build one with
+/// the `bind_*` methods at startup and hand it to `InputArbiter::new`.
+pub struct InputBinding {
+    bindings: HashMap<RawInput, InputEffect>,
+    mouse_axes: Vec<ActionId>,
+}
+
+impl InputBinding {
+    pub fn new() -> Self {
+        InputBinding {
+            bindings: HashMap::new(),
+            mouse_axes: Vec::new(),
+        }
+    }
+
+    /// Binds a key to a digital button action.
+    pub fn bind_key_to_button(mut self, key: Keycode, action: &str) -> Self {
+        self.bindings.insert(RawInput::Key(key), InputEffect::Button(action.to_string()));
+        self
+    }
+
+    /// Binds a mouse button to a digital button action.
+    pub fn bind_mouse_button_to_button(mut self, button: MouseButton, action: &str) -> Self {
+        self.bindings
+            .insert(RawInput::MouseButton(button), InputEffect::Button(action.to_string()));
+        self
+    }
+
+    /// Binds a key to contribute `scale` to an axis while held.  Bind
+    /// two keys to the same axis with opposite `scale`s to get a classic
+    /// "left/right" -1..1 axis.
+    pub fn bind_key_to_axis(mut self, key: Keycode, action: &str, scale: f32) -> Self {
+        self.bindings.insert(RawInput::Key(key),
+                              InputEffect::Axis {
+                                  id: action.to_string(),
+                                  scale: scale,
+                                  deadzone: 0.0,
+                              });
+        self
+    }
+
+    /// Binds horizontal (or vertical) mouse motion to an axis, scaling
+    /// each frame's relative delta by `scale` and zeroing anything
+    /// smaller than `deadzone`.
+    pub fn bind_mouse_motion_to_axis(mut self,
+                                     horizontal: bool,
+                                     action: &str,
+                                     scale: f32,
+                                     deadzone: f32)
+                                     -> Self {
+        self.mouse_axes.push(action.to_string());
+        self.bindings.insert(RawInput::MouseAxis { horizontal: horizontal },
+                              InputEffect::Axis {
+                                  id: action.to_string(),
+                                  scale: scale,
+                                  deadzone: deadzone,
+                              });
+        self
+    }
+
+    fn get(&self, input: &RawInput) -> Option<&InputEffect> {
+        self.bindings.get(input)
+    }
+}
+
+/// The state of a single digital button action for this frame.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct ButtonState {
+    /// True on the frame the button transitioned from up to down.
+    pub pressed: bool,
+    /// True on the frame the button transitioned from down to up.
+    pub released: bool,
+    /// True for every frame the button is held down.
+    pub held: bool,
+}
+
+/// The logical input state for the current frame: which named buttons
+/// are pressed/held/released, and the current value of each named axis.
+/// Scenes poll this instead of handling raw ggez events.
+pub struct InputState {
+    buttons: BTreeMap<ActionId, ButtonState>,
+    axes: BTreeMap<ActionId, f32>,
+}
+
+impl InputState {
+    fn new() -> Self {
+        InputState {
+            buttons: BTreeMap::new(),
+            axes: BTreeMap::new(),
+        }
+    }
+
+    /// The state of a button action.  Unbound or never-touched actions
+    /// read as all-false.
+    pub fn get_button(&self, action: &str) -> ButtonState {
+        self.buttons.get(action).cloned().unwrap_or_default()
+    }
+
+    /// The current value of an axis action.  Unbound or never-touched
+    /// axes read as 0.0.
+    pub fn get_axis(&self, action: &str) -> f32 {
+        self.axes.get(action).cloned().unwrap_or(0.0)
+    }
+
+    fn clear_edges(&mut self) {
+        for button in self.buttons.values_mut() {
+            button.pressed = false;
+            button.released = false;
+        }
+    }
+}
+
+/// Turns raw ggez input callbacks into the named-action `InputState` a
+/// `Scene` can poll.  Owned by `SceneManager`, which forwards every raw
+/// event here and clears the per-frame edges after each `update`.
+pub struct InputArbiter {
+    bindings: InputBinding,
+    state: InputState,
+    /// Raw inputs currently contributing to an axis, so OS key-repeat
+    /// doesn't re-add `scale` on every repeated `press` -- mirrors what
+    /// `ButtonState.held` does for digital buttons.
+    held_axes: HashSet<RawInput>,
+}
+
+impl InputArbiter {
+    pub fn new(bindings: InputBinding) -> Self {
+        InputArbiter {
+            bindings: bindings,
+            state: InputState::new(),
+            held_axes: HashSet::new(),
+        }
+    }
+
+    /// The current frame's logical input state.
+    pub fn state(&self) -> &InputState {
+        &self.state
+    }
+
+    pub fn key_down_event(&mut self, keycode: Keycode) {
+        self.press(RawInput::Key(keycode));
+    }
+
+    pub fn key_up_event(&mut self, keycode: Keycode) {
+        self.release(RawInput::Key(keycode));
+    }
+
+    pub fn mouse_button_down_event(&mut self, button: MouseButton) {
+        self.press(RawInput::MouseButton(button));
+    }
+
+    pub fn mouse_button_up_event(&mut self, button: MouseButton) {
+        self.release(RawInput::MouseButton(button));
+    }
+
+    pub fn mouse_motion_event(&mut self, xrel: i32, yrel: i32) {
+        self.apply_axis_delta(RawInput::MouseAxis { horizontal: true }, xrel as f32);
+        self.apply_axis_delta(RawInput::MouseAxis { horizontal: false }, yrel as f32);
+    }
+
+    /// Clears the per-frame pressed/released edges and zeroes mouse-motion
+    /// axes, which are relative deltas rather than held state.  Call once
+    /// per update, after the current scene has had a chance to poll this
+    /// frame's input.
+    pub fn end_frame(&mut self) {
+        self.state.clear_edges();
+        for id in &self.bindings.mouse_axes {
+            self.state.axes.insert(id.clone(), 0.0);
+        }
+    }
+
+    fn press(&mut self, input: RawInput) {
+        match self.bindings.get(&input).cloned() {
+            Some(InputEffect::Button(action)) => {
+                let button = self.state.buttons.entry(action).or_insert_with(Default::default);
+                if !button.held {
+                    button.pressed = true;
+                }
+                button.held = true;
+            }
+            Some(InputEffect::Axis { id, scale, deadzone }) => {
+                if !self.held_axes.insert(input) {
+                    // Already held -- this is OS key-repeat, not a new
+                    // press, so don't add `scale` again.
+                    return;
+                }
+                let axis = self.state.axes.entry(id).or_insert(0.0);
+                *axis += scale;
+                if axis.abs() < deadzone {
+                    *axis = 0.0;
+                }
+            }
+            None => {}
+        }
+    }
+
+    fn release(&mut self, input: RawInput) {
+        match self.bindings.get(&input).cloned() {
+            Some(InputEffect::Button(action)) => {
+                let button = self.state.buttons.entry(action).or_insert_with(Default::default);
+                button.held = false;
+                button.released = true;
+            }
+            Some(InputEffect::Axis { id, scale, .. }) => {
+                self.held_axes.remove(&input);
+                let axis = self.state.axes.entry(id).or_insert(0.0);
+                *axis -= scale;
+            }
+            None => {}
+        }
+    }
+
+    fn apply_axis_delta(&mut self, input: RawInput, delta: f32) {
+        if let Some(InputEffect::Axis { id, scale, deadzone }) = self.bindings.get(&input).cloned() {
+            let axis = self.state.axes.entry(id).or_insert(0.0);
+            *axis += scale * delta;
+            if axis.abs() < deadzone {
+                *axis = 0.0;
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{InputArbiter, InputBinding};
+    use ggez::event::{Keycode, MouseButton};
+
+    #[test]
+    fn test_button_edges() {
+        let bindings = InputBinding::new().bind_key_to_button(Keycode::Space, "jump");
+        let mut arbiter = InputArbiter::new(bindings);
+
+        let button = arbiter.state().get_button("jump");
+        assert!(!button.held && !button.pressed && !button.released);
+
+        arbiter.key_down_event(Keycode::Space);
+        let button = arbiter.state().get_button("jump");
+        assert!(button.held && button.pressed && !button.released);
+
+        arbiter.end_frame();
+        let button = arbiter.state().get_button("jump");
+        assert!(button.held && !button.pressed && !button.released);
+
+        arbiter.key_up_event(Keycode::Space);
+        let button = arbiter.state().get_button("jump");
+        assert!(!button.held && button.released);
+    }
+
+    #[test]
+    fn test_key_axis() {
+        let bindings = InputBinding::new()
+            .bind_key_to_axis(Keycode::Left, "move_x", -1.0)
+            .bind_key_to_axis(Keycode::Right, "move_x", 1.0);
+        let mut arbiter = InputArbiter::new(bindings);
+
+        assert_eq!(arbiter.state().get_axis("move_x"), 0.0);
+
+        arbiter.key_down_event(Keycode::Right);
+        assert_eq!(arbiter.state().get_axis("move_x"), 1.0);
+
+        arbiter.key_down_event(Keycode::Left);
+        assert_eq!(arbiter.state().get_axis("move_x"), 0.0);
+
+        arbiter.key_up_event(Keycode::Left);
+        assert_eq!(arbiter.state().get_axis("move_x"), 1.0);
+    }
+
+    #[test]
+    fn test_mouse_motion_axis_decays_each_frame() {
+        let bindings = InputBinding::new().bind_mouse_motion_to_axis(true, "look_x", 0.5, 0.0);
+        let mut arbiter = InputArbiter::new(bindings);
+
+        arbiter.mouse_motion_event(4, 0);
+        assert_eq!(arbiter.state().get_axis("look_x"), 2.0);
+
+        arbiter.end_frame();
+        assert_eq!(arbiter.state().get_axis("look_x"), 0.0);
+    }
+
+    #[test]
+    fn test_key_axis_ignores_repeat() {
+        let bindings = InputBinding::new()
+            .bind_key_to_axis(Keycode::Left, "move_x", -1.0)
+            .bind_key_to_axis(Keycode::Right, "move_x", 1.0);
+        let mut arbiter = InputArbiter::new(bindings);
+
+        // ggez fires key_down_event repeatedly while a key is held down;
+        // the axis must not keep accumulating `scale` on every repeat.
+        arbiter.key_down_event(Keycode::Right);
+        arbiter.key_down_event(Keycode::Right);
+        arbiter.key_down_event(Keycode::Right);
+        assert_eq!(arbiter.state().get_axis("move_x"), 1.0);
+
+        arbiter.key_up_event(Keycode::Right);
+        assert_eq!(arbiter.state().get_axis("move_x"), 0.0);
+    }
+
+    #[test]
+    fn test_unbound_mouse_button_is_ignored() {
+        let bindings = InputBinding::new();
+        let mut arbiter = InputArbiter::new(bindings);
+        arbiter.mouse_button_down_event(MouseButton::Left);
+        let button = arbiter.state().get_button("fire");
+        assert!(!button.held);
+    }
+}
@@ -5,17 +5,105 @@ use ggez::conf;
 use ggez::event;
 use ggez::event::EventHandler;
 
+use input::{InputArbiter, InputBinding, InputState};
+
+#[cfg(feature = "serde-save")]
+use serde;
+#[cfg(feature = "serde-save")]
+use serde::{Serialize, Deserialize};
+#[cfg(feature = "serde-save")]
+use serde_json;
+
+use std::any::Any;
 use std::collections::BTreeMap;
+use std::collections::VecDeque;
 use std::time::Duration;
+#[cfg(feature = "hot-reload")]
+use std::time::SystemTime;
 
 
 pub trait SavedScene {
     fn load(&self) -> Box<Scene>;
     fn name(&self) -> &str;
+
+    /// Lets save-game code (see the `serde-save` feature) downcast a
+    /// `&SavedScene` trait object back to its concrete type.  Trait
+    /// objects can't be upcast to `&Any` on their own, so this has to be
+    /// threaded through explicitly; implementors should just return `self`.
+    fn as_any(&self) -> &Any;
+}
+
+/// A command returned from `Scene::update` telling the `SceneManager`
+/// what, if anything, to do with the scene stack once the update
+/// returns.  This replaces signalling a transition out-of-band through a
+/// stashed scene name.
+pub enum SceneSwitch {
+    /// Nothing to do; stay on the current scene.
+    None,
+    /// Pop the current scene off the stack, revealing the one beneath it.
+    Pop,
+    /// Push a freshly-loaded scene on top of the current one.
+    Push(Box<SavedScene>),
+    /// Unload the current scene and replace it with a named scene from
+    /// the store.
+    Replace(String),
+    /// Unload every scene on the stack and replace it with a single named
+    /// scene from the store.
+    ReplaceAll(String),
 }
 
-pub trait Scene: EventHandler {
+pub trait Scene {
+    fn update(&mut self,
+              ctx: &mut ggez::Context,
+              dt: Duration,
+              input: &InputState)
+              -> GameResult<SceneSwitch>;
+
+    fn draw(&mut self, ctx: &mut ggez::Context) -> GameResult<()>;
+
     fn unload(&mut self) -> Box<SavedScene>;
+
+    /// Whether the scene beneath this one in the stack should still be
+    /// drawn.  Defaults to false; override it for pause menus, dialog
+    /// boxes, and other scenes that only cover part of the screen so the
+    /// scene underneath keeps showing through.
+    fn transparent(&self) -> bool {
+        false
+    }
+
+    fn mouse_button_down_event(&mut self, _button: event::MouseButton, _x: i32, _y: i32) {}
+
+    fn mouse_button_up_event(&mut self, _button: event::MouseButton, _x: i32, _y: i32) {}
+
+    fn mouse_motion_event(&mut self,
+                          _state: event::MouseState,
+                          _x: i32,
+                          _y: i32,
+                          _xrel: i32,
+                          _yrel: i32) {
+    }
+
+    fn mouse_wheel_event(&mut self, _x: i32, _y: i32) {}
+
+    fn key_down_event(&mut self,
+                      _keycode: event::Keycode,
+                      _keymod: event::Mod,
+                      _repeat: bool) {
+    }
+
+    fn key_up_event(&mut self,
+                    _keycode: event::Keycode,
+                    _keymod: event::Mod,
+                    _repeat: bool) {
+    }
+
+    fn focus_event(&mut self, _gained: bool) {}
+
+    /// Called upon a quit event.  If it returns true,
+    /// the game does not exit.
+    fn quit_event(&mut self) -> bool {
+        false
+    }
 }
 
 /// The GameData trait just provides
@@ -33,48 +121,101 @@ pub trait GameData
 
 pub struct SceneStore<T> {
     states: BTreeMap<String, Box<SavedScene>>,
+    #[cfg(feature = "serde-save")]
+    save_tags: BTreeMap<String, String>,
     pub game_data: T,
 }
 
 impl<T> SceneStore<T> {
     pub fn add<S: SavedScene + 'static>(&mut self, scene_state: S) {
-        self.states.insert(scene_state.name().to_string(), Box::new(scene_state));
+        self.add_boxed(Box::new(scene_state));
+    }
+
+    fn add_boxed(&mut self, scene_state: Box<SavedScene>) {
+        self.states.insert(scene_state.name().to_string(), scene_state);
+    }
+
+    /// Like `add`, but also remembers `type_tag` against this scene's
+    /// instance name so `SceneManager::save_game` can serialize it later
+    /// through a `SaveRegistry` registered under the same tag.
+    #[cfg(feature = "serde-save")]
+    pub fn add_saveable<S>(&mut self, scene_state: S, type_tag: &'static str)
+        where S: SavedScene + 'static
+    {
+        self.save_tags.insert(scene_state.name().to_string(), type_tag.to_string());
+        self.add(scene_state);
     }
 }
 
 /// A SceneManager is a GameState that handles Scene's
 /// and switches from one to another when requested.
 ///
+/// Scenes are kept in a stack rather than a single slot, so a scene such
+/// as a pause menu or dialog box can be pushed on top of the scene
+/// beneath it instead of replacing it outright.  Only the topmost scene
+/// is updated and receives input, while `draw` walks the stack from the
+/// bottom up so transparent scenes let the ones underneath show through.
+///
+/// Transitions are signalled by the `SceneSwitch` a scene's `update`
+/// returns, rather than by stashing a scene name on the side; the
+/// manager applies the command right after `update` returns.
+///
+/// Raw ggez input events are also fed through an `InputArbiter`, which
+/// turns them into named-action `InputState` that gets handed to the
+/// current scene's `update` so scenes can poll bindings instead of
+/// matching on keycodes.
+///
 /// The stuff you would normally store in your GameState
 /// type should implement GameData and go into the T type.
 pub struct SceneManager<T> {
     store: SceneStore<T>,
-    current: Box<Scene>,
-    next_scene: Option<String>,
+    scenes: VecDeque<Box<Scene>>,
+    input: InputArbiter,
+    /// Mirrors `scenes`' names, bottom-to-top, so hot reload can tell
+    /// whether a given scene is the one currently running without having
+    /// to unload it just to read its name off of it -- `Scene` itself
+    /// carries no `name()`, only `SavedScene` does.
+    #[cfg(feature = "hot-reload")]
+    scene_names: VecDeque<String>,
+    #[cfg(feature = "hot-reload")]
+    watched: BTreeMap<String, WatchedScene>,
+    #[cfg(feature = "hot-reload")]
+    hot_reload_registry: Option<SaveRegistry>,
 }
 
 
 impl<T> EventHandler for SceneManager<T>
 {
     fn update(&mut self, ctx: &mut ggez::Context, dt: Duration) -> GameResult<()> {
-        // TODO: Get rid of this hacky clone!
-        if let Some(ref scene_name) = self.next_scene.clone() {
-            self.switch_scene(&scene_name)?;
-        }
-        self.current.update(ctx, dt)?;
-        Ok(())
+        self.poll_hot_reload(ctx)?;
+        let switch = {
+            let SceneManager { ref mut scenes, ref input, .. } = *self;
+            let top = scenes.back_mut().expect("SceneManager scene stack is empty");
+            top.update(ctx, dt, input.state())?
+        };
+        self.input.end_frame();
+        self.apply_switch(switch)
     }
 
     fn draw(&mut self, ctx: &mut ggez::Context) -> GameResult<()> {
-        self.current.draw(ctx)
+        let bottom = self.scenes
+            .iter()
+            .rposition(|scene| !scene.transparent())
+            .unwrap_or(0);
+        for scene in self.scenes.iter_mut().skip(bottom) {
+            scene.draw(ctx)?;
+        }
+        Ok(())
     }
 
     fn mouse_button_down_event(&mut self, button: event::MouseButton, x: i32, y: i32) {
-        self.current.mouse_button_down_event(button, x, y)
+        self.input.mouse_button_down_event(button);
+        self.top_mut().mouse_button_down_event(button, x, y)
     }
 
     fn mouse_button_up_event(&mut self, button: event::MouseButton, x: i32, y: i32) {
-        self.current.mouse_button_up_event(button, x, y)
+        self.input.mouse_button_up_event(button);
+        self.top_mut().mouse_button_up_event(button, x, y)
     }
 
     fn mouse_motion_event(&mut self,
@@ -83,76 +224,163 @@ impl<T> EventHandler for SceneManager<T>
                           _y: i32,
                           _xrel: i32,
                           _yrel: i32) {
-        self.current.mouse_motion_event(_state, _x, _y, _xrel, _yrel)
+        self.input.mouse_motion_event(_xrel, _yrel);
+        self.top_mut().mouse_motion_event(_state, _x, _y, _xrel, _yrel)
     }
 
     fn mouse_wheel_event(&mut self, _x: i32, _y: i32) {
-        self.current.mouse_wheel_event(_x, _y)
+        self.top_mut().mouse_wheel_event(_x, _y)
     }
 
     fn key_down_event(&mut self,
                       _keycode: event::Keycode,
                       _keymod: event::Mod,
                       _repeat: bool) {
-        self.current.key_down_event(_keycode, _keymod, _repeat)
+        self.input.key_down_event(_keycode);
+        self.top_mut().key_down_event(_keycode, _keymod, _repeat)
     }
 
     fn key_up_event(&mut self,
                     _keycode: event::Keycode,
                     _keymod: event::Mod,
                     _repeat: bool) {
-        self.current.key_up_event(_keycode, _keymod, _repeat)
+        self.input.key_up_event(_keycode);
+        self.top_mut().key_up_event(_keycode, _keymod, _repeat)
     }
 
     fn focus_event(&mut self, _gained: bool) {
-        self.current.focus_event(_gained)
+        self.top_mut().focus_event(_gained)
     }
 
     /// Called upon a quit event.  If it returns true,
     /// the game does not exit.
     fn quit_event(&mut self) -> bool {
-        self.current.quit_event()
+        self.top_mut().quit_event()
     }
 }
 
 impl<T> SceneManager<T> {
     /// This lets us create a SceneManager by providing the data for it,
     /// instead of having it implicitly created via the GameData trait.
-    fn new(starting_scene_state: Box<SavedScene>, game_data: T) -> Self {
+    fn new(starting_scene_state: Box<SavedScene>, game_data: T, input_bindings: InputBinding) -> Self {
         let starting_scene = starting_scene_state.load();
+        let starting_name = starting_scene_state.name().to_string();
         let mut scenes: BTreeMap<String, Box<SavedScene>> = BTreeMap::new();
-        scenes.insert(starting_scene_state.name().to_string(),
-                      starting_scene_state);
+        scenes.insert(starting_name.clone(), starting_scene_state);
         let store = SceneStore {
             states: scenes,
+            #[cfg(feature = "serde-save")]
+            save_tags: BTreeMap::new(),
 
             game_data: game_data,
         };
+        let mut stack = VecDeque::new();
+        stack.push_back(starting_scene);
         let sm = SceneManager {
-            current: starting_scene,
+            scenes: stack,
             store: store,
-            next_scene: None,
+            input: InputArbiter::new(input_bindings),
+            #[cfg(feature = "hot-reload")]
+            scene_names: {
+                let mut names = VecDeque::new();
+                names.push_back(starting_name);
+                names
+            },
+            #[cfg(feature = "hot-reload")]
+            watched: BTreeMap::new(),
+            #[cfg(feature = "hot-reload")]
+            hot_reload_registry: None,
         };
         sm
     }
 
+    /// The logical input state for the current frame, as computed by the
+    /// `InputArbiter` from this frame's raw ggez events.
+    pub fn input(&self) -> &InputState {
+        self.input.state()
+    }
+
+    /// The currently active (topmost) scene.
     pub fn current(&self) -> &Scene {
-        &*self.current
+        &**self.scenes.back().expect("SceneManager scene stack is empty")
     }
 
+    /// The currently active (topmost) scene.
     pub fn current_mut(&mut self) -> &mut Scene {
-        &mut *self.current
+        &mut **self.scenes.back_mut().expect("SceneManager scene stack is empty")
+    }
+
+    fn top_mut(&mut self) -> &mut Box<Scene> {
+        self.scenes.back_mut().expect("SceneManager scene stack is empty")
+    }
+
+    /// Applies a `SceneSwitch` command returned from the topmost scene's
+    /// `update`.  `Replace` is handed to `switch_scene`, which does the
+    /// unload/store/load of the topmost scene.
+    fn apply_switch(&mut self, switch: SceneSwitch) -> GameResult<()> {
+        match switch {
+            SceneSwitch::None => Ok(()),
+            SceneSwitch::Pop => self.pop_scene(),
+            SceneSwitch::Push(scene_state) => {
+                #[cfg(feature = "hot-reload")]
+                self.scene_names.push_back(scene_state.name().to_string());
+                self.scenes.push_back(scene_state.load());
+                Ok(())
+            }
+            SceneSwitch::Replace(scene_name) => self.switch_scene(&scene_name),
+            SceneSwitch::ReplaceAll(scene_name) => self.replace_all_scenes(&scene_name),
+        }
+    }
+
+    /// Pushes a new scene on top of the current one.  The scene
+    /// underneath is left running (but not updated or given input) so it
+    /// can still be drawn if the new scene declares itself transparent.
+    pub fn push_scene(&mut self, scene_name: &str) -> GameResult<()> {
+        if let Some(scene_state) = self.store.states.get_mut(scene_name) {
+            let new_scene = scene_state.load();
+            self.scenes.push_back(new_scene);
+            #[cfg(feature = "hot-reload")]
+            self.scene_names.push_back(scene_name.to_string());
+            Ok(())
+        } else {
+            let msg = format!("SceneManager: Asked to push scene {} but it did not exist?",
+                              scene_name);
+            Err(ggez::GameError::ResourceNotFound(msg, vec![]))
+        }
+    }
+
+    /// Pops the topmost scene off the stack, unloading it and saving its
+    /// state, revealing the scene beneath it.  The scene stack is never
+    /// allowed to go empty.
+    pub fn pop_scene(&mut self) -> GameResult<()> {
+        if self.scenes.len() <= 1 {
+            let msg = "SceneManager: Asked to pop the last scene on the stack".to_string();
+            return Err(ggez::GameError::ResourceNotFound(msg, vec![]));
+        }
+        let mut old_scene = self.scenes.pop_back().expect("just checked len() > 1");
+        let old_scene_state = old_scene.unload();
+        self.store.add_boxed(old_scene_state);
+        #[cfg(feature = "hot-reload")]
+        self.scene_names.pop_back();
+        Ok(())
     }
 
+    /// Replaces the topmost scene with a different one, unloading and
+    /// saving the old one in the process.  The rest of the stack is left
+    /// untouched.
     pub fn switch_scene(&mut self, scene_name: &str) -> GameResult<()> {
         // Save current scene
-        let old_scene_state = self.current.unload();
-        let old_scene_name = old_scene_state.name().to_string();
-        self.store.states.insert(old_scene_name, old_scene_state);
+        let old_scene_state = self.top_mut().unload();
+        self.store.add_boxed(old_scene_state);
         // Then load the new one.
         if let Some(scene_state) = self.store.states.get_mut(scene_name) {
             let new_scene = scene_state.load();
-            self.current = new_scene;
+            *self.top_mut() = new_scene;
+            #[cfg(feature = "hot-reload")]
+            {
+                self.scene_names.pop_back();
+                self.scene_names.push_back(scene_name.to_string());
+            }
             Ok(())
         } else {
             let msg = format!("SceneManager: Asked to load scene {} but it did not exist?",
@@ -160,6 +388,408 @@ impl<T> SceneManager<T> {
             Err(ggez::GameError::ResourceNotFound(msg, vec![]))
         }
     }
+
+    /// Unloads and saves every scene on the stack, then replaces it with
+    /// a single named scene from the store.
+    pub fn replace_all_scenes(&mut self, scene_name: &str) -> GameResult<()> {
+        if !self.store.states.contains_key(scene_name) {
+            let msg = format!("SceneManager: Asked to load scene {} but it did not exist?",
+                              scene_name);
+            return Err(ggez::GameError::ResourceNotFound(msg, vec![]));
+        }
+        while let Some(mut scene) = self.scenes.pop_back() {
+            let old_scene_state = scene.unload();
+            self.store.add_boxed(old_scene_state);
+        }
+        let scene_state = self.store
+            .states
+            .get_mut(scene_name)
+            .expect("just checked contains_key");
+        self.scenes.push_back(scene_state.load());
+        #[cfg(feature = "hot-reload")]
+        {
+            self.scene_names.clear();
+            self.scene_names.push_back(scene_name.to_string());
+        }
+        Ok(())
+    }
+}
+
+/// Serializes a scene's saved state once its concrete type is known --
+/// this is what lets `SaveRegistry` turn a type-erased `Box<SavedScene>`
+/// into JSON without `SceneStore` having to know about every scene type
+/// ahead of time.
+#[cfg(feature = "serde-save")]
+type SceneSerializer = fn(&Any) -> serde_json::Result<serde_json::Value>;
+
+#[cfg(feature = "serde-save")]
+type SceneDeserializer = fn(serde_json::Value) -> serde_json::Result<Box<SavedScene>>;
+
+/// Maps a scene type's `type_tag` (as passed to `SceneStore::add_saveable`)
+/// to the serializer/deserializer pair that can round-trip it through
+/// JSON.  Register every saveable scene type here once at startup, then
+/// pass the registry to `SceneManager::save_game`/`load_game`.
+#[cfg(feature = "serde-save")]
+#[derive(Default)]
+pub struct SaveRegistry {
+    serializers: BTreeMap<&'static str, SceneSerializer>,
+    deserializers: BTreeMap<&'static str, SceneDeserializer>,
+}
+
+#[cfg(feature = "serde-save")]
+impl SaveRegistry {
+    pub fn new() -> Self {
+        SaveRegistry::default()
+    }
+
+    /// Registers `S` under `type_tag`, which must be unique and stable
+    /// across saves -- it's what `load_game` uses to find this function
+    /// pair again.
+    pub fn register<S>(&mut self, type_tag: &'static str)
+        where S: SavedScene + serde::Serialize + serde::de::DeserializeOwned + 'static
+    {
+        fn serialize<S>(value: &Any) -> serde_json::Result<serde_json::Value>
+            where S: SavedScene + serde::Serialize + 'static
+        {
+            let scene = value.downcast_ref::<S>()
+                .expect("SaveRegistry: type_tag registered against the wrong type");
+            serde_json::to_value(scene)
+        }
+        fn deserialize<S>(value: serde_json::Value) -> serde_json::Result<Box<SavedScene>>
+            where S: SavedScene + serde::de::DeserializeOwned + 'static
+        {
+            let scene: S = serde_json::from_value(value)?;
+            Ok(Box::new(scene))
+        }
+        self.serializers.insert(type_tag, serialize::<S>);
+        self.deserializers.insert(type_tag, deserialize::<S>);
+    }
+}
+
+#[cfg(feature = "serde-save")]
+#[derive(Serialize, Deserialize)]
+struct SavedEntry {
+    type_tag: String,
+    state: serde_json::Value,
+}
+
+#[cfg(feature = "serde-save")]
+#[derive(Serialize)]
+struct SaveFileRef<'a, T: 'a> {
+    current: &'a str,
+    entries: &'a BTreeMap<String, SavedEntry>,
+    game_data: &'a T,
+}
+
+#[cfg(feature = "serde-save")]
+#[derive(Deserialize)]
+struct SaveFileOwned<T> {
+    current: String,
+    entries: BTreeMap<String, SavedEntry>,
+    game_data: T,
+}
+
+#[cfg(feature = "serde-save")]
+impl<T> SceneManager<T> {
+    /// Serializes every `add_saveable`-registered scene in the `SceneStore`,
+    /// plus `game_data`, to `path` through ggez's `Filesystem`.  The
+    /// currently running scene is unloaded and reloaded around the save
+    /// so its state is captured too; scenes that were never added via
+    /// `add_saveable` are silently left out of the save, unless the
+    /// current scene itself isn't saveable, in which case this returns an
+    /// error rather than write a save file with no state for `current`.
+    pub fn save_game(&mut self,
+                     ctx: &mut ggez::Context,
+                     path: &str,
+                     registry: &SaveRegistry)
+                     -> GameResult<()>
+        where T: serde::Serialize
+    {
+        let json = self.build_save_payload(registry)?;
+        use std::io::Write;
+        let mut file = ggez::filesystem::create(ctx, path)?;
+        file.write_all(&json).map_err(ggez::GameError::IOError)
+    }
+
+    /// Does the actual unload/serialize/restore work for `save_game`,
+    /// stopping short of writing the result anywhere -- split out so it
+    /// can be tested without needing a `ggez::Context` to write a file
+    /// through.
+    fn build_save_payload(&mut self, registry: &SaveRegistry) -> GameResult<Vec<u8>>
+        where T: serde::Serialize
+    {
+        let current_state = self.top_mut().unload();
+        let current_name = current_state.name().to_string();
+        if !self.store.save_tags.contains_key(&current_name) {
+            // Put it back the way we found it before bailing.
+            *self.top_mut() = current_state.load();
+            let msg = format!("SceneManager: current scene {} was never added via \
+                               add_saveable, so save_game can't capture it",
+                              current_name);
+            return Err(ggez::GameError::ResourceNotFound(msg, vec![]));
+        }
+        self.store.add_boxed(current_state);
+
+        let mut entries = BTreeMap::new();
+        for (name, type_tag) in self.store.save_tags.iter() {
+            let scene_state = self.store
+                .states
+                .get(name)
+                .expect("SceneStore: save_tags and states got out of sync");
+            let serializer = registry.serializers
+                .get(type_tag.as_str())
+                .ok_or_else(|| {
+                    let msg = format!("SaveRegistry: no serializer registered for tag {}",
+                                      type_tag);
+                    ggez::GameError::ResourceNotFound(msg, vec![])
+                })?;
+            let value = serializer(scene_state.as_any())
+                .map_err(|e| ggez::GameError::ResourceLoadError(e.to_string()))?;
+            entries.insert(name.clone(),
+                           SavedEntry {
+                               type_tag: type_tag.to_string(),
+                               state: value,
+                           });
+        }
+
+        let save_file = SaveFileRef {
+            current: &current_name,
+            entries: &entries,
+            game_data: &self.store.game_data,
+        };
+        let json = serde_json::to_vec_pretty(&save_file)
+            .map_err(|e| ggez::GameError::ResourceLoadError(e.to_string()))?;
+
+        // Put the current scene back so the game keeps running.  It's
+        // already resident in the store from the `add_boxed` above, so
+        // load it straight back out instead of routing through
+        // `switch_scene`, which would unload the live scene a second
+        // time purely to put it right back.
+        let scene_state = self.store
+            .states
+            .get_mut(&current_name)
+            .expect("current_name was just added to the store above");
+        *self.top_mut() = scene_state.load();
+        Ok(json)
+    }
+
+    /// Replaces this manager's entire `SceneStore` and scene stack with
+    /// the save file written by `save_game`, resuming on whichever scene
+    /// was current when it was saved.
+    pub fn load_game(&mut self,
+                     ctx: &mut ggez::Context,
+                     path: &str,
+                     registry: &SaveRegistry)
+                     -> GameResult<()>
+        where T: serde::de::DeserializeOwned
+    {
+        let mut contents = String::new();
+        {
+            use std::io::Read;
+            let mut file = ggez::filesystem::open(ctx, path)?;
+            file.read_to_string(&mut contents).map_err(ggez::GameError::IOError)?;
+        }
+        self.apply_save_payload(&contents, registry)
+    }
+
+    /// Does the actual deserialize/restore work for `load_game`, starting
+    /// from the save file's raw contents -- split out so it can be tested
+    /// without needing a `ggez::Context` to read a file through.
+    fn apply_save_payload(&mut self, contents: &str, registry: &SaveRegistry) -> GameResult<()>
+        where T: serde::de::DeserializeOwned
+    {
+        let save_file: SaveFileOwned<T> = serde_json::from_str(contents)
+            .map_err(|e| ggez::GameError::ResourceLoadError(e.to_string()))?;
+        // Bind this ahead of the loop below: that loop moves
+        // `save_file.entries` by value, and the `ok_or_else` closure
+        // further down captures `save_file` as a whole to read
+        // `save_file.current`, which would otherwise be a use of a
+        // partially-moved value.
+        let current = save_file.current;
+
+        let mut states: BTreeMap<String, Box<SavedScene>> = BTreeMap::new();
+        let mut save_tags = BTreeMap::new();
+        for (name, entry) in save_file.entries {
+            let deserializer = registry.deserializers
+                .get(entry.type_tag.as_str())
+                .ok_or_else(|| {
+                    let msg = format!("SaveRegistry: no deserializer registered for tag {}",
+                                      entry.type_tag);
+                    ggez::GameError::ResourceNotFound(msg, vec![])
+                })?;
+            let scene_state = deserializer(entry.state)
+                .map_err(|e| ggez::GameError::ResourceLoadError(e.to_string()))?;
+            save_tags.insert(name.clone(), entry.type_tag.clone());
+            states.insert(name, scene_state);
+        }
+
+        let starting_scene = states
+            .get_mut(&current)
+            .ok_or_else(|| {
+                let msg = format!("Save file names {} as the current scene, but it has no \
+                                   saved state",
+                                  current);
+                ggez::GameError::ResourceNotFound(msg, vec![])
+            })?
+            .load();
+
+        self.store = SceneStore {
+            states: states,
+            save_tags: save_tags,
+            game_data: save_file.game_data,
+        };
+        self.scenes.clear();
+        self.scenes.push_back(starting_scene);
+        #[cfg(feature = "hot-reload")]
+        {
+            self.scene_names.clear();
+            self.scene_names.push_back(current);
+        }
+        Ok(())
+    }
+}
+
+/// A scene file watched for development-time hot reload, plus the mtime
+/// it had the last time we checked.
+#[cfg(feature = "hot-reload")]
+struct WatchedScene {
+    path: String,
+    last_modified: Option<SystemTime>,
+}
+
+#[cfg(feature = "hot-reload")]
+impl<T> SceneManager<T> {
+    /// Turns on hot reload, using `registry` to deserialize whatever
+    /// scene files get watched with `watch_scene_file`.
+    pub fn enable_hot_reload(&mut self, registry: SaveRegistry) {
+        self.hot_reload_registry = Some(registry);
+    }
+
+    /// Watches `path` for changes; once `enable_hot_reload` has been
+    /// called, every `update` will notice when it changes on disk and
+    /// hot-`reload_scene` the scene named `name`.  `name` must already
+    /// have been added via `SceneStore::add_saveable` so its type tag is
+    /// known.
+    pub fn watch_scene_file(&mut self, name: &str, path: &str) {
+        self.watched.insert(name.to_string(),
+                            WatchedScene {
+                                path: path.to_string(),
+                                last_modified: None,
+                            });
+    }
+
+    pub fn stop_watching_scene_file(&mut self, name: &str) {
+        self.watched.remove(name);
+    }
+
+    /// Re-reads the file watched for `name` and replaces its stored
+    /// state.  If `name` is the scene currently on top of the stack, it's
+    /// unloaded and immediately reloaded so the change takes effect live;
+    /// otherwise the running scenes are left untouched and only the
+    /// stored state is refreshed for next time it's loaded.
+    pub fn reload_scene(&mut self, ctx: &mut ggez::Context, name: &str) -> GameResult<()> {
+        let watch_path = self.watched
+            .get(name)
+            .map(|watch| watch.path.clone())
+            .ok_or_else(|| {
+                let msg = format!("SceneManager: {} is not a watched scene", name);
+                ggez::GameError::ResourceNotFound(msg, vec![])
+            })?;
+        let type_tag = self.store
+            .save_tags
+            .get(name)
+            .cloned()
+            .ok_or_else(|| {
+                let msg = format!("SceneManager: {} was never added via add_saveable, so it \
+                                   has no save type tag to reload with",
+                                  name);
+                ggez::GameError::ResourceNotFound(msg, vec![])
+            })?;
+
+        let new_state = {
+            let registry = self.hot_reload_registry
+                .as_ref()
+                .ok_or_else(|| {
+                    let msg = "SceneManager: hot reload is not enabled".to_string();
+                    ggez::GameError::ResourceNotFound(msg, vec![])
+                })?;
+            let deserializer = registry.deserializers
+                .get(type_tag.as_str())
+                .ok_or_else(|| {
+                    let msg = format!("SaveRegistry: no deserializer registered for tag {}",
+                                      type_tag);
+                    ggez::GameError::ResourceNotFound(msg, vec![])
+                })?;
+            let mut contents = String::new();
+            {
+                use std::io::Read;
+                let mut file = ggez::filesystem::open(ctx, &watch_path)?;
+                file.read_to_string(&mut contents).map_err(ggez::GameError::IOError)?;
+            }
+            let value: serde_json::Value = serde_json::from_str(&contents)
+                .map_err(|e| ggez::GameError::ResourceLoadError(e.to_string()))?;
+            deserializer(value).map_err(|e| ggez::GameError::ResourceLoadError(e.to_string()))?
+        };
+
+        self.apply_reloaded_state(name, new_state);
+        Ok(())
+    }
+
+    /// Swaps `new_state` in for `name`.  If `name` is the scene currently
+    /// on top of the stack it's live-reloaded in place; otherwise only the
+    /// stored state is refreshed, and the running scenes are left alone.
+    /// Split out of `reload_scene` so the swap-in decision can be tested
+    /// without needing a `ggez::Context` to read a file through.
+    fn apply_reloaded_state(&mut self, name: &str, new_state: Box<SavedScene>) {
+        // Check the tracked current-scene name instead of unloading the
+        // top scene speculatively just to inspect its name -- unloading
+        // a scene we weren't asked to touch would be destructive for any
+        // `unload()` that isn't a pure/idempotent capture.
+        if self.is_scene_current(name) {
+            self.top_mut().unload();
+            *self.top_mut() = new_state.load();
+        }
+        self.store.add_boxed(new_state);
+    }
+
+    fn is_scene_current(&self, name: &str) -> bool {
+        self.scene_names.back().map(String::as_str) == Some(name)
+    }
+
+    /// Checks every watched scene's backing file for a changed mtime and
+    /// `reload_scene`s any that changed.  Cheap when nothing changed,
+    /// since it's just a `stat` per watched scene.
+    fn poll_hot_reload(&mut self, ctx: &mut ggez::Context) -> GameResult<()> {
+        if self.hot_reload_registry.is_none() {
+            return Ok(());
+        }
+        let changed: Vec<String> = {
+            let mut changed = Vec::new();
+            for (name, watch) in self.watched.iter_mut() {
+                let modified = ggez::filesystem::metadata(ctx, &watch.path)
+                    .ok()
+                    .and_then(|metadata| metadata.modified().ok());
+                if modified.is_some() && modified != watch.last_modified {
+                    watch.last_modified = modified;
+                    changed.push(name.clone());
+                }
+            }
+            changed
+        };
+        for name in &changed {
+            self.reload_scene(ctx, name)?;
+        }
+        Ok(())
+    }
+}
+
+/// When the `hot-reload` feature is off, `update` still calls this, it
+/// just has nothing to do.
+#[cfg(not(feature = "hot-reload"))]
+impl<T> SceneManager<T> {
+    fn poll_hot_reload(&mut self, _ctx: &mut ggez::Context) -> GameResult<()> {
+        Ok(())
+    }
 }
 
 #[cfg(test)]
@@ -167,13 +797,19 @@ mod tests {
 
     use ggez;
     use ggez::GameResult;
-    use ggez::event::EventHandler;
 
+    use std::any::Any;
     use std::time::Duration;
 
-    use super::{Scene, SavedScene, SceneManager, SceneStore};
+    use input::{InputBinding, InputState};
+    use super::{Scene, SavedScene, SceneManager, SceneStore, SceneSwitch};
+    #[cfg(feature = "serde-save")]
+    use super::SaveRegistry;
+    #[cfg(feature = "serde-save")]
+    use serde::{Serialize, Deserialize};
 
     #[derive(Clone, Debug)]
+    #[cfg_attr(feature = "serde-save", derive(Serialize, Deserialize))]
     struct TestSavedScene {
         value: i32,
         name: String,
@@ -186,17 +822,21 @@ mod tests {
         fn name(&self) -> &str {
             &self.name
         }
+        fn as_any(&self) -> &Any {
+            self
+        }
     }
 
     #[derive(Clone, Debug)]
     struct TestScene(TestSavedScene);
 
-    impl EventHandler for TestScene {        
+    impl Scene for TestScene {
         fn update(&mut self,
                   _ctx: &mut ggez::Context,
-                  _dt: Duration)
-                  -> GameResult<()> {
-            Ok(())
+                  _dt: Duration,
+                  _input: &InputState)
+                  -> GameResult<SceneSwitch> {
+            Ok(SceneSwitch::None)
         }
 
         fn draw(&mut self,
@@ -205,9 +845,6 @@ mod tests {
             Ok(())
         }
 
-    }
-
-    impl Scene for TestScene {
         fn unload(&mut self) -> Box<SavedScene> {
             Box::new(self.0.clone())
         }
@@ -223,7 +860,7 @@ mod tests {
             name: "other scene".to_string(),
             value: 23,
         };
-        let mut sm = SceneManager::new(Box::new(default_scene), ());
+        let mut sm = SceneManager::new(Box::new(default_scene), (), InputBinding::new());
         sm.store.add(new_scene);
 
         {
@@ -242,4 +879,228 @@ mod tests {
         assert!(res.is_err());
     }
 
+    #[test]
+    fn test_scene_push_pop() {
+        let default_scene = TestSavedScene {
+            name: "default scene".to_string(),
+            value: 42,
+        };
+        let overlay_scene = TestSavedScene {
+            name: "overlay scene".to_string(),
+            value: 7,
+        };
+        let mut sm = SceneManager::new(Box::new(default_scene), (), InputBinding::new());
+        sm.store.add(overlay_scene);
+
+        let res = sm.push_scene("overlay scene");
+        assert!(res.is_ok());
+        assert_eq!(sm.scenes.len(), 2);
+
+        {
+            let s = sm.current_mut().unload();
+            assert_eq!(s.name(), "overlay scene");
+        }
+
+        let res = sm.pop_scene();
+        assert!(res.is_ok());
+        assert_eq!(sm.scenes.len(), 1);
+
+        {
+            let s = sm.current_mut().unload();
+            assert_eq!(s.name(), "default scene");
+        }
+
+        let res = sm.pop_scene();
+        assert!(res.is_err());
+    }
+
+    #[test]
+    fn test_scene_switch_command() {
+        let default_scene = TestSavedScene {
+            name: "default scene".to_string(),
+            value: 42,
+        };
+        let other_scene = TestSavedScene {
+            name: "other scene".to_string(),
+            value: 23,
+        };
+        let mut sm = SceneManager::new(Box::new(default_scene), (), InputBinding::new());
+        sm.store.add(other_scene);
+
+        let res = sm.apply_switch(SceneSwitch::Push(Box::new(TestSavedScene {
+            name: "pushed scene".to_string(),
+            value: 1,
+        })));
+        assert!(res.is_ok());
+        assert_eq!(sm.scenes.len(), 2);
+
+        let res = sm.apply_switch(SceneSwitch::Pop);
+        assert!(res.is_ok());
+        assert_eq!(sm.scenes.len(), 1);
+
+        let res = sm.apply_switch(SceneSwitch::Replace("other scene".to_string()));
+        assert!(res.is_ok());
+        {
+            let s = sm.current_mut().unload();
+            assert_eq!(s.name(), "other scene");
+        }
+
+        let res = sm.apply_switch(SceneSwitch::ReplaceAll("default scene".to_string()));
+        assert!(res.is_ok());
+        assert_eq!(sm.scenes.len(), 1);
+        {
+            let s = sm.current_mut().unload();
+            assert_eq!(s.name(), "default scene");
+        }
+    }
+
+    #[cfg(feature = "hot-reload")]
+    #[test]
+    fn test_reload_current_scene_applies_live() {
+        let default_scene = TestSavedScene {
+            name: "default scene".to_string(),
+            value: 42,
+        };
+        let mut sm = SceneManager::new(Box::new(default_scene), (), InputBinding::new());
+
+        sm.apply_reloaded_state("default scene",
+                                Box::new(TestSavedScene {
+                                    name: "default scene".to_string(),
+                                    value: 99,
+                                }));
+
+        let s = sm.current_mut().unload();
+        assert_eq!(s.name(), "default scene");
+        assert_eq!(s.as_any().downcast_ref::<TestSavedScene>().unwrap().value, 99);
+    }
+
+    #[cfg(feature = "hot-reload")]
+    #[test]
+    fn test_reload_background_scene_leaves_current_untouched() {
+        let default_scene = TestSavedScene {
+            name: "default scene".to_string(),
+            value: 42,
+        };
+        let overlay_scene = TestSavedScene {
+            name: "overlay scene".to_string(),
+            value: 7,
+        };
+        let mut sm = SceneManager::new(Box::new(default_scene), (), InputBinding::new());
+        sm.store.add(overlay_scene);
+        sm.push_scene("overlay scene").expect("overlay scene was just added to the store");
+
+        // "default scene" is no longer on top; reloading it must not touch
+        // the running "overlay scene", only its stored snapshot.
+        sm.apply_reloaded_state("default scene",
+                                Box::new(TestSavedScene {
+                                    name: "default scene".to_string(),
+                                    value: 99,
+                                }));
+
+        let s = sm.current_mut().unload();
+        assert_eq!(s.name(), "overlay scene");
+        assert_eq!(s.as_any().downcast_ref::<TestSavedScene>().unwrap().value, 7);
+
+        let stored = sm.store
+            .states
+            .get("default scene")
+            .expect("default scene should still be in the store");
+        assert_eq!(stored.as_any().downcast_ref::<TestSavedScene>().unwrap().value,
+                  99);
+    }
+
+    #[cfg(feature = "serde-save")]
+    #[test]
+    fn test_save_and_load_round_trip() {
+        let default_scene = TestSavedScene {
+            name: "default scene".to_string(),
+            value: 42,
+        };
+        let other_scene = TestSavedScene {
+            name: "other scene".to_string(),
+            value: 7,
+        };
+        let mut sm = SceneManager::new(Box::new(default_scene), (), InputBinding::new());
+        sm.store.add_saveable(other_scene, "test_scene");
+        sm.switch_scene("other scene").expect("other scene was just added to the store");
+
+        let mut registry = SaveRegistry::new();
+        registry.register::<TestSavedScene>("test_scene");
+
+        let json = sm.build_save_payload(&registry).expect("current scene is saveable");
+
+        // The save shouldn't have left the running scene unloaded.
+        {
+            let s = sm.current_mut().unload();
+            assert_eq!(s.name(), "other scene");
+            assert_eq!(s.as_any().downcast_ref::<TestSavedScene>().unwrap().value, 7);
+        }
+
+        let contents = String::from_utf8(json).expect("save payload is valid utf8");
+        sm.apply_save_payload(&contents, &registry).expect("save payload round-trips");
+
+        let s = sm.current_mut().unload();
+        assert_eq!(s.name(), "other scene");
+        assert_eq!(s.as_any().downcast_ref::<TestSavedScene>().unwrap().value, 7);
+    }
+
+    #[cfg(feature = "serde-save")]
+    #[test]
+    fn test_save_game_rejects_unsaveable_current_scene() {
+        let default_scene = TestSavedScene {
+            name: "default scene".to_string(),
+            value: 42,
+        };
+        let mut sm = SceneManager::new(Box::new(default_scene), (), InputBinding::new());
+        // "default scene" was never added via `add_saveable`.
+        let registry = SaveRegistry::new();
+
+        let res = sm.build_save_payload(&registry);
+        assert!(res.is_err());
+
+        // The current scene must be left exactly as it was, not corrupted
+        // by the aborted unload.
+        let s = sm.current_mut().unload();
+        assert_eq!(s.name(), "default scene");
+        assert_eq!(s.as_any().downcast_ref::<TestSavedScene>().unwrap().value, 42);
+    }
+
+    #[cfg(all(feature = "serde-save", feature = "hot-reload"))]
+    #[test]
+    fn test_load_game_resets_current_scene_tracking() {
+        let default_scene = TestSavedScene {
+            name: "default scene".to_string(),
+            value: 42,
+        };
+        let other_scene = TestSavedScene {
+            name: "other scene".to_string(),
+            value: 7,
+        };
+        let mut sm = SceneManager::new(Box::new(default_scene), (), InputBinding::new());
+        sm.store.add_saveable(other_scene, "test_scene");
+        sm.switch_scene("other scene").expect("other scene was just added to the store");
+
+        let mut registry = SaveRegistry::new();
+        registry.register::<TestSavedScene>("test_scene");
+        let json = sm.build_save_payload(&registry).expect("current scene is saveable");
+        let contents = String::from_utf8(json).expect("save payload is valid utf8");
+
+        // Push a scene on top so the live stack no longer matches what it
+        // was when the save was taken.
+        sm.store
+            .add(TestSavedScene {
+                     name: "pushed scene".to_string(),
+                     value: 0,
+                 });
+        sm.push_scene("pushed scene").expect("pushed scene was just added to the store");
+
+        sm.apply_save_payload(&contents, &registry).expect("save payload round-trips");
+
+        // `load_game` must have reset the tracked current-scene name to
+        // match the freshly-loaded stack, not left it pointing at
+        // "pushed scene" from before the load.
+        assert!(sm.is_scene_current("other scene"));
+        assert!(!sm.is_scene_current("pushed scene"));
+    }
+
 }